@@ -1,8 +1,8 @@
 extern crate numeru;
 
-use numeru::data_structures::ds_array::array_static::ArrayStatic;
+use numeru::StaticArray;
 
 fn main() {
-    let a: ArrayStatic = ArrayStatic { capacity: 10 };
+    let a: StaticArray = StaticArray::zeros(vec![2, 5]);
     println!("{}", a);
 }
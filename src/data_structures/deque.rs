@@ -0,0 +1,307 @@
+// Double-ended queue backed by a growable ring buffer
+
+use std::iter::{Chain, Take};
+use std::slice;
+
+pub struct Deque<T> {
+    // Unused slots are `None`; the elements live at `head..head + len`,
+    // wrapping around the end of the buffer
+    buf: Vec<Option<T>>,
+    head: usize,
+    len: usize,
+}
+
+impl<T> Deque<T> {
+    pub fn new() -> Self {
+        Deque {
+            buf: Vec::new(),
+            head: 0,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_front(&mut self, elem: T) {
+        self.grow_if_full();
+        self.head = (self.head + self.buf.len() - 1) % self.buf.len();
+        self.buf[self.head] = Some(elem);
+        self.len += 1;
+    }
+
+    pub fn push_back(&mut self, elem: T) {
+        self.grow_if_full();
+        let index = self.physical(self.len);
+        self.buf[index] = Some(elem);
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let elem = self.buf[self.head].take();
+        self.head = (self.head + 1) % self.buf.len();
+        self.len -= 1;
+        elem
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let index = self.physical(self.len);
+        self.buf[index].take()
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.buf[self.head].as_ref()
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.buf[self.physical(self.len - 1)].as_ref()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (wrapped, from_head) = self.buf.split_at(self.head);
+        Iter {
+            slots: from_head.iter().chain(wrapped.iter()).take(self.len),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let len = self.len;
+        let (wrapped, from_head) = self.buf.split_at_mut(self.head);
+        IterMut {
+            slots: from_head.iter_mut().chain(wrapped.iter_mut()).take(len),
+        }
+    }
+
+    fn physical(&self, index: usize) -> usize {
+        (self.head + index) % self.buf.len()
+    }
+
+    fn grow_if_full(&mut self) {
+        if self.len < self.buf.len() {
+            return;
+        }
+        let capacity = (self.buf.len() * 2).max(4);
+        let mut buf = Vec::with_capacity(capacity);
+        for i in 0..self.len {
+            let index = self.physical(i);
+            buf.push(self.buf[index].take());
+        }
+        buf.resize_with(capacity, || None);
+        self.buf = buf;
+        self.head = 0;
+    }
+}
+
+impl<T> Default for Deque<T> {
+    fn default() -> Self {
+        Deque::new()
+    }
+}
+
+pub struct IntoIter<T>(Deque<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+}
+
+type Slots<'a, T> = Take<Chain<slice::Iter<'a, Option<T>>, slice::Iter<'a, Option<T>>>>;
+type SlotsMut<'a, T> = Take<Chain<slice::IterMut<'a, Option<T>>, slice::IterMut<'a, Option<T>>>>;
+
+// Occupied slots are contiguous from `head`, wrapping around the end of the
+// buffer, so the first `len` slots of the chain are exactly the elements
+pub struct Iter<'a, T> {
+    slots: Slots<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots
+            .next()
+            .map(|slot| slot.as_ref().expect("occupied slot"))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.slots.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+pub struct IterMut<'a, T> {
+    slots: SlotsMut<'a, T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots
+            .next()
+            .map(|slot| slot.as_mut().expect("occupied slot"))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.slots.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> IntoIterator for Deque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Deque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Deque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue() {
+        let mut deque = Deque::new();
+        assert_eq!(deque.pop_front(), None);
+        for i in 0..10 {
+            deque.push_back(i);
+        }
+        assert_eq!(deque.len(), 10);
+        assert_eq!(deque.front(), Some(&0));
+        assert_eq!(deque.back(), Some(&9));
+        for i in 0..10 {
+            assert_eq!(deque.pop_front(), Some(i));
+        }
+        assert!(deque.is_empty());
+        assert_eq!(deque.front(), None);
+    }
+
+    #[test]
+    fn stack() {
+        let mut deque = Deque::new();
+        assert_eq!(deque.pop_back(), None);
+        for i in 0..10 {
+            deque.push_back(i);
+        }
+        for i in (0..10).rev() {
+            assert_eq!(deque.pop_back(), Some(i));
+        }
+
+        for i in 0..10 {
+            deque.push_front(i);
+        }
+        for i in (0..10).rev() {
+            assert_eq!(deque.pop_front(), Some(i));
+        }
+        assert_eq!(deque.back(), None);
+    }
+
+    #[test]
+    fn wrap_around() {
+        let mut deque = Deque::new();
+        for i in 0..3 {
+            deque.push_back(i);
+        }
+        deque.pop_front();
+        deque.pop_front();
+        // Fills the buffer across its end, then forces a regrow
+        for i in 3..8 {
+            deque.push_back(i);
+        }
+        deque.push_front(1);
+        assert_eq!(
+            deque.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5, 6, 7]
+        );
+        assert_eq!(deque.len(), 7);
+    }
+
+    #[test]
+    fn iterators() {
+        let mut deque = Deque::new();
+        deque.push_back(2);
+        deque.push_back(3);
+        deque.push_front(1);
+
+        for elem in deque.iter_mut() {
+            *elem *= 10;
+        }
+        for elem in &mut deque {
+            *elem += 1;
+        }
+        assert_eq!((&deque).into_iter().count(), 3);
+        assert_eq!(deque.into_iter().collect::<Vec<_>>(), vec![11, 21, 31]);
+    }
+
+    #[test]
+    fn iter_size_after_shrinking() {
+        let mut deque = Deque::new();
+        for i in 0..1000 {
+            deque.push_back(i);
+        }
+        while deque.len() > 2 {
+            deque.pop_front();
+        }
+        assert_eq!(deque.iter().size_hint(), (2, Some(2)));
+        assert_eq!(deque.iter_mut().len(), 2);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), vec![998, 999]);
+
+        deque.pop_front();
+        deque.pop_front();
+        assert_eq!(deque.iter().len(), 0);
+        assert_eq!(Deque::<i32>::new().iter().next(), None);
+    }
+
+    #[test]
+    fn long_deque_drop() {
+        let mut deque = Deque::new();
+        for i in 0..100_000 {
+            deque.push_back(i.to_string());
+        }
+        assert_eq!(deque.back(), Some(&String::from("99999")));
+        drop(deque);
+    }
+}
@@ -1,12 +1,14 @@
+#![allow(deprecated)]
+
 use std::fmt;
 
+#[deprecated(note = "use `StaticArray`, which stores its shape and elements")]
 pub struct ArrayStatic {
     pub capacity: u32,
 }
 
 impl fmt::Display for ArrayStatic {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // Customize so only `x` and `y` are denoted.
         write!(f, "capacity: {}", self.capacity)
     }
 }
@@ -20,4 +22,10 @@ mod tests {
         let a: ArrayStatic = ArrayStatic { capacity: 4 };
         assert_eq!(a.capacity, 4);
     }
+
+    #[test]
+    fn display() {
+        let a: ArrayStatic = ArrayStatic { capacity: 4 };
+        assert_eq!(a.to_string(), "capacity: 4");
+    }
 }
@@ -1 +1,2 @@
 pub mod array_static;
+pub mod static_array;
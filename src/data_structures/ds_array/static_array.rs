@@ -0,0 +1,564 @@
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowType {
+    Hann,
+    Hamming,
+    Blackman,
+    Bartlett,
+    Rectangular,
+}
+
+/// Fixed-size n-dimensional array of `f32` stored in row-major order.
+///
+/// `PartialEq` compares shapes and elements exactly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaticArray {
+    pub shape: Vec<usize>,
+    pub capacity: usize,
+    pub data: Vec<f32>,
+}
+
+impl StaticArray {
+    pub fn new(shape: Vec<usize>, data: Vec<f32>) -> StaticArray {
+        let capacity: usize = shape.iter().product();
+        assert_eq!(
+            capacity,
+            data.len(),
+            "shape {:?} needs {} elements, got {}",
+            shape,
+            capacity,
+            data.len()
+        );
+        StaticArray {
+            shape,
+            capacity,
+            data,
+        }
+    }
+
+    pub fn zeros(shape: Vec<usize>) -> StaticArray {
+        let capacity = shape.iter().product();
+        StaticArray::new(shape, vec![0.0; capacity])
+    }
+
+    pub fn l1_norm(&self) -> f32 {
+        self.data.iter().fold(0.0, |acc, x| acc + x.abs())
+    }
+
+    pub fn l2_norm(&self) -> f32 {
+        self.data.iter().fold(0.0, |acc, x| acc + x * x).sqrt()
+    }
+
+    pub fn linf_norm(&self) -> f32 {
+        self.data.iter().fold(0.0, |acc: f32, x| acc.max(x.abs()))
+    }
+
+    /// Symmetric window of length `n`, matching numpy's `hanning`, `hamming`,
+    /// `blackman` and `bartlett`.
+    pub fn window_coefficients(window_type: WindowType, n: usize) -> StaticArray {
+        if n == 1 {
+            return StaticArray::new(vec![1], vec![1.0]);
+        }
+        let denom = n.saturating_sub(1) as f64;
+        let data = (0..n)
+            .map(|k| {
+                let x = k as f64 / denom;
+                let w = match window_type {
+                    WindowType::Hann => 0.5 - 0.5 * (2.0 * PI * x).cos(),
+                    WindowType::Hamming => 0.54 - 0.46 * (2.0 * PI * x).cos(),
+                    WindowType::Blackman => {
+                        0.42 - 0.5 * (2.0 * PI * x).cos() + 0.08 * (4.0 * PI * x).cos()
+                    }
+                    WindowType::Bartlett => 1.0 - (2.0 * x - 1.0).abs(),
+                    WindowType::Rectangular => 1.0,
+                };
+                w as f32
+            })
+            .collect();
+        StaticArray::new(vec![n], data)
+    }
+
+    /// Multiplies a 1D array by the window of the same length.
+    pub fn apply_window(&self, window_type: WindowType) -> StaticArray {
+        assert_eq!(
+            self.shape.len(),
+            1,
+            "windows apply to 1D arrays, got shape {:?}",
+            self.shape
+        );
+        let window = StaticArray::window_coefficients(window_type, self.capacity);
+        let data = self
+            .data
+            .iter()
+            .zip(window.data.iter())
+            .map(|(x, w)| x * w)
+            .collect();
+        StaticArray::new(self.shape.clone(), data)
+    }
+
+    /// Bilinearly interpolated value of a 2D array at a fractional
+    /// `(row, col)`. Coordinates outside the array are clamped to the edge.
+    pub fn bilinear_interpolate(&self, row: f32, col: f32) -> f32 {
+        assert_eq!(
+            self.shape.len(),
+            2,
+            "interpolation needs a 2D array, got shape {:?}",
+            self.shape
+        );
+        let (rows, cols) = (self.shape[0], self.shape[1]);
+        let row = row.clamp(0.0, (rows - 1) as f32);
+        let col = col.clamp(0.0, (cols - 1) as f32);
+
+        let (r0, c0) = (row.floor() as usize, col.floor() as usize);
+        let (r1, c1) = ((r0 + 1).min(rows - 1), (c0 + 1).min(cols - 1));
+        let (fr, fc) = (row - r0 as f32, col - c0 as f32);
+        let at = |r: usize, c: usize| self.data[r * cols + c];
+
+        let top = at(r0, c0) * (1.0 - fc) + at(r0, c1) * fc;
+        let bottom = at(r1, c0) * (1.0 - fc) + at(r1, c1) * fc;
+        top * (1.0 - fr) + bottom * fr
+    }
+
+    /// Like [`StaticArray::bilinear_interpolate`], but returns `None` for
+    /// coordinates outside the array instead of clamping.
+    pub fn bilinear_interpolate_checked(&self, row: f32, col: f32) -> Option<f32> {
+        let inside = |x: f32, len: usize| x >= 0.0 && x <= (len as f32 - 1.0);
+        if self.shape.len() == 2 && inside(row, self.shape[0]) && inside(col, self.shape[1]) {
+            Some(self.bilinear_interpolate(row, col))
+        } else {
+            None
+        }
+    }
+
+    /// `[height, width, 3]` array from interleaved RGB bytes, scaled to
+    /// `[0, 1]`.
+    pub fn from_image_rgb(pixels: &[u8], height: usize, width: usize) -> StaticArray {
+        assert_eq!(
+            pixels.len(),
+            height * width * 3,
+            "expected {}x{} RGB pixels",
+            height,
+            width
+        );
+        let data = pixels.iter().map(|&p| p as f32 / 255.0).collect();
+        StaticArray::new(vec![height, width, 3], data)
+    }
+
+    /// Interleaved RGB bytes from a `[height, width, 3]` array, clamping to
+    /// `[0, 1]` before scaling back to `[0, 255]`.
+    pub fn to_image_rgb(&self) -> Vec<u8> {
+        assert!(
+            self.shape.len() == 3 && self.shape[2] == 3,
+            "expected shape [height, width, 3], got {:?}",
+            self.shape
+        );
+        self.data
+            .iter()
+            .map(|x| (x.clamp(0.0, 1.0) * 255.0).round() as u8)
+            .collect()
+    }
+
+    /// Binary dilation of a 2D 0/1 array: a pixel is set if any pixel under
+    /// the kernel's nonzero entries is set. The kernel is centred on each
+    /// pixel and positions outside the array are ignored.
+    pub fn dilate(&self, kernel: &StaticArray) -> StaticArray {
+        self.morphology(kernel, true)
+    }
+
+    /// Binary erosion of a 2D 0/1 array: a pixel is set only if every pixel
+    /// under the kernel's nonzero entries is set. Positions outside the array
+    /// are ignored, so shapes touching the border are not eroded by it.
+    pub fn erode(&self, kernel: &StaticArray) -> StaticArray {
+        self.morphology(kernel, false)
+    }
+
+    /// Every `(multi_index, value)` pair in row-major order.
+    pub fn to_index_value_pairs(&self) -> Vec<(Vec<usize>, f32)> {
+        let mut index = vec![0; self.shape.len()];
+        let mut pairs = Vec::with_capacity(self.capacity);
+        for &value in &self.data {
+            pairs.push((index.clone(), value));
+            // Advance the multi-index like an odometer, last axis fastest
+            for axis in (0..index.len()).rev() {
+                index[axis] += 1;
+                if index[axis] < self.shape[axis] {
+                    break;
+                }
+                index[axis] = 0;
+            }
+        }
+        pairs
+    }
+
+    /// Array of the given shape with each pair's value at its index and zeros
+    /// everywhere else. Later pairs overwrite earlier ones at the same index.
+    pub fn from_index_value_pairs(pairs: &[(Vec<usize>, f32)], shape: Vec<usize>) -> StaticArray {
+        let mut out = StaticArray::zeros(shape);
+        for (index, value) in pairs {
+            assert!(
+                index.len() == out.shape.len()
+                    && index.iter().zip(out.shape.iter()).all(|(i, n)| i < n),
+                "index {:?} is out of bounds for shape {:?}",
+                index,
+                out.shape
+            );
+            let flat = index
+                .iter()
+                .zip(out.shape.iter())
+                .fold(0, |acc, (i, n)| acc * n + i);
+            out.data[flat] = *value;
+        }
+        out
+    }
+
+    /// Run-length encoding of the flattened data as `(value, run_length)`
+    /// pairs. Values are compared by bit pattern, so runs of NaN are merged.
+    pub fn encode_rle(&self) -> Vec<(f32, usize)> {
+        let mut runs: Vec<(f32, usize)> = Vec::new();
+        for &x in &self.data {
+            match runs.last_mut() {
+                Some((value, len)) if value.to_bits() == x.to_bits() => *len += 1,
+                _ => runs.push((x, 1)),
+            }
+        }
+        runs
+    }
+
+    pub fn decode_rle(runs: &[(f32, usize)], shape: Vec<usize>) -> StaticArray {
+        let data = runs
+            .iter()
+            .flat_map(|&(value, len)| std::iter::repeat_n(value, len))
+            .collect();
+        StaticArray::new(shape, data)
+    }
+
+    /// Labels the 4-connected regions of nonzero pixels in a 2D array.
+    ///
+    /// Returns an array of the same shape holding `0` for background and
+    /// `1..=count` for the regions, along with the number of regions.
+    pub fn connected_components(&self) -> (StaticArray, usize) {
+        assert_eq!(
+            self.shape.len(),
+            2,
+            "labelling needs a 2D array, got shape {:?}",
+            self.shape
+        );
+        let (rows, cols) = (self.shape[0], self.shape[1]);
+        let mut labels = StaticArray::zeros(self.shape.clone());
+        let mut count = 0;
+        let mut queue = VecDeque::new();
+
+        for start in 0..self.capacity {
+            if self.data[start] == 0.0 || labels.data[start] != 0.0 {
+                continue;
+            }
+            count += 1;
+            labels.data[start] = count as f32;
+            queue.push_back(start);
+            while let Some(index) = queue.pop_front() {
+                let (r, c) = (index / cols, index % cols);
+                let neighbours = [
+                    (r > 0).then(|| index - cols),
+                    (r + 1 < rows).then(|| index + cols),
+                    (c > 0).then(|| index - 1),
+                    (c + 1 < cols).then(|| index + 1),
+                ];
+                for next in neighbours.into_iter().flatten() {
+                    if self.data[next] != 0.0 && labels.data[next] == 0.0 {
+                        labels.data[next] = count as f32;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        (labels, count)
+    }
+
+    fn morphology(&self, kernel: &StaticArray, dilate: bool) -> StaticArray {
+        assert!(
+            self.shape.len() == 2 && kernel.shape.len() == 2,
+            "morphology needs 2D arrays, got shapes {:?} and {:?}",
+            self.shape,
+            kernel.shape
+        );
+        let (rows, cols) = (self.shape[0], self.shape[1]);
+        let (k_rows, k_cols) = (kernel.shape[0], kernel.shape[1]);
+        let (center_r, center_c) = (k_rows / 2, k_cols / 2);
+
+        let mut out = StaticArray::zeros(self.shape.clone());
+        for r in 0..rows {
+            for c in 0..cols {
+                let mut covered = (0..k_rows)
+                    .flat_map(|kr| (0..k_cols).map(move |kc| (kr, kc)))
+                    .filter(|&(kr, kc)| kernel.data[kr * k_cols + kc] != 0.0)
+                    .filter_map(|(kr, kc)| {
+                        let rr = (r + kr).checked_sub(center_r).filter(|&x| x < rows)?;
+                        let cc = (c + kc).checked_sub(center_c).filter(|&x| x < cols)?;
+                        Some(self.data[rr * cols + cc] != 0.0)
+                    });
+                let set = if dilate {
+                    covered.any(|x| x)
+                } else {
+                    covered.all(|x| x)
+                };
+                out.data[r * cols + c] = if set { 1.0 } else { 0.0 };
+            }
+        }
+        out
+    }
+
+    fn fmt_axis(&self, f: &mut fmt::Formatter, axis: usize, offset: usize) -> fmt::Result {
+        if axis == self.shape.len() {
+            return write!(f, "{}", self.data[offset]);
+        }
+        // Number of elements spanned by one step along `axis`
+        let stride: usize = self.shape[axis + 1..].iter().product();
+        write!(f, "[")?;
+        for i in 0..self.shape[axis] {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            self.fmt_axis(f, axis + 1, offset + i * stride)?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl fmt::Display for StaticArray {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_axis(f, 0, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create() {
+        let a = StaticArray::new(vec![2, 3], vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(a.capacity, 6);
+        assert_eq!(StaticArray::zeros(vec![4]).data, vec![0.0; 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn create_wrong_length() {
+        StaticArray::new(vec![2, 2], vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn display() {
+        let a = StaticArray::new(vec![2, 3], vec![1.0, 2.0, 3.0, 4.0, 5.5, 6.0]);
+        assert_eq!(a.to_string(), "[[1, 2, 3], [4, 5.5, 6]]");
+        let a = StaticArray::new(vec![3], vec![1.0, 2.0, 3.0]);
+        assert_eq!(a.to_string(), "[1, 2, 3]");
+        let a = StaticArray::new(vec![], vec![7.0]);
+        assert_eq!(a.to_string(), "7");
+        let a = StaticArray::new(vec![2, 1, 2], vec![1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(a.to_string(), "[[[1, 2]], [[3, 4]]]");
+    }
+
+    #[test]
+    fn norms() {
+        let a = StaticArray::new(vec![2], vec![3.0, -4.0]);
+        assert_eq!(a.l1_norm(), 7.0);
+        assert_eq!(a.l2_norm(), 5.0);
+        assert_eq!(a.linf_norm(), 4.0);
+    }
+
+    #[test]
+    fn windows() {
+        let hann = StaticArray::window_coefficients(WindowType::Hann, 9);
+        assert!(hann.data[0].abs() < 1e-6);
+        assert!(hann.data[8].abs() < 1e-6);
+        assert!((hann.data[4] - 1.0).abs() < 1e-6);
+        assert!((hann.data[2] - 0.5).abs() < 1e-6);
+
+        let hamming = StaticArray::window_coefficients(WindowType::Hamming, 9);
+        assert!((hamming.data[0] - 0.08).abs() < 1e-6);
+        assert!((hamming.data[4] - 1.0).abs() < 1e-6);
+
+        let blackman = StaticArray::window_coefficients(WindowType::Blackman, 9);
+        assert!(blackman.data[0].abs() < 1e-6);
+        assert!((blackman.data[4] - 1.0).abs() < 1e-6);
+
+        let bartlett = StaticArray::window_coefficients(WindowType::Bartlett, 5);
+        assert_eq!(bartlett.data, vec![0.0, 0.5, 1.0, 0.5, 0.0]);
+
+        let rect = StaticArray::window_coefficients(WindowType::Rectangular, 4);
+        assert_eq!(rect.data, vec![1.0; 4]);
+        assert_eq!(
+            StaticArray::window_coefficients(WindowType::Hann, 1).data,
+            vec![1.0]
+        );
+    }
+
+    #[test]
+    fn apply_window() {
+        let a = StaticArray::new(vec![5], vec![2.0; 5]);
+        assert_eq!(
+            a.apply_window(WindowType::Bartlett).data,
+            vec![0.0, 1.0, 2.0, 1.0, 0.0]
+        );
+        assert_eq!(a.apply_window(WindowType::Rectangular), a);
+    }
+
+    #[test]
+    fn bilinear_interpolate() {
+        let a = StaticArray::new(vec![2, 3], vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        for r in 0..2 {
+            for c in 0..3 {
+                assert_eq!(
+                    a.bilinear_interpolate(r as f32, c as f32),
+                    a.data[r * 3 + c]
+                );
+            }
+        }
+        assert_eq!(
+            a.bilinear_interpolate(0.5, 0.5),
+            (1.0 + 2.0 + 4.0 + 5.0) / 4.0
+        );
+        assert_eq!(a.bilinear_interpolate(0.0, 1.5), 2.5);
+
+        // Clamped to the edges
+        assert_eq!(a.bilinear_interpolate(-1.0, -1.0), 1.0);
+        assert_eq!(a.bilinear_interpolate(5.0, 1.5), 5.5);
+
+        assert_eq!(a.bilinear_interpolate_checked(0.5, 0.5), Some(3.0));
+        assert_eq!(a.bilinear_interpolate_checked(1.0, 2.0), Some(6.0));
+        assert_eq!(a.bilinear_interpolate_checked(-0.1, 0.0), None);
+        assert_eq!(a.bilinear_interpolate_checked(0.0, 2.1), None);
+        assert_eq!(a.bilinear_interpolate_checked(f32::NAN, 0.0), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bilinear_interpolate_not_2d() {
+        StaticArray::zeros(vec![4]).bilinear_interpolate(0.0, 0.0);
+    }
+
+    #[test]
+    fn image_rgb() {
+        let pixels: Vec<u8> = vec![0, 255, 128, 10, 20, 30, 255, 0, 0, 1, 2, 254];
+        let a = StaticArray::from_image_rgb(&pixels, 2, 2);
+        assert_eq!(a.shape, vec![2, 2, 3]);
+        assert_eq!(a.data[0], 0.0);
+        assert_eq!(a.data[1], 1.0);
+        assert!((a.data[2] - 128.0 / 255.0).abs() < 1e-6);
+        assert_eq!(a.to_image_rgb(), pixels);
+
+        let out_of_range = StaticArray::new(vec![1, 1, 3], vec![-0.5, 1.5, 0.5]);
+        assert_eq!(out_of_range.to_image_rgb(), vec![0, 255, 128]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn image_rgb_wrong_shape() {
+        StaticArray::zeros(vec![2, 2, 4]).to_image_rgb();
+    }
+
+    #[test]
+    fn dilate_erode() {
+        let mut a = StaticArray::zeros(vec![7, 7]);
+        for r in 1..4 {
+            for c in 1..4 {
+                a.data[r * 7 + c] = 1.0;
+            }
+        }
+        let mut noisy = a.clone();
+        noisy.data[5 * 7 + 5] = 1.0;
+        let kernel = StaticArray::new(vec![3, 3], vec![1.0; 9]);
+
+        let eroded = noisy.erode(&kernel);
+        assert_eq!(eroded.data.iter().sum::<f32>(), 1.0);
+        assert_eq!(eroded.data[2 * 7 + 2], 1.0);
+
+        let dilated = noisy.dilate(&kernel);
+        // The grown square (5x5) and grown dot (3x3) overlap at (4, 4)
+        assert_eq!(dilated.data.iter().sum::<f32>(), 25.0 + 9.0 - 1.0);
+
+        // Opening (erode, then dilate) drops the dot and keeps the square
+        assert_eq!(noisy.erode(&kernel).dilate(&kernel), a);
+    }
+
+    #[test]
+    fn erode_ignores_border() {
+        let a = StaticArray::new(vec![2, 2], vec![1.0; 4]);
+        let kernel = StaticArray::new(vec![3, 3], vec![1.0; 9]);
+        assert_eq!(a.erode(&kernel), a);
+    }
+
+    #[test]
+    fn connected_components() {
+        #[rustfmt::skip]
+        let a = StaticArray::new(
+            vec![5, 5],
+            vec![
+                1.0, 1.0, 0.0, 0.0, 0.0,
+                1.0, 0.0, 0.0, 0.0, 0.0,
+                0.0, 0.0, 0.0, 1.0, 1.0,
+                0.0, 0.0, 0.0, 0.0, 1.0,
+                0.0, 0.0, 0.0, 1.0, 1.0,
+            ],
+        );
+        let (labels, count) = a.connected_components();
+        assert_eq!(count, 2);
+        assert_eq!(labels.data[0], 1.0);
+        assert_eq!(labels.data[5], 1.0);
+        assert_eq!(labels.data[2 * 5 + 3], 2.0);
+        assert_eq!(labels.data[4 * 5 + 3], 2.0);
+        assert_eq!(labels.data[12], 0.0);
+
+        // Diagonal neighbours are not 4-connected
+        let diagonal = StaticArray::new(vec![2, 2], vec![1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(diagonal.connected_components().1, 2);
+        assert_eq!(StaticArray::zeros(vec![3, 3]).connected_components().1, 0);
+    }
+
+    #[test]
+    fn rle() {
+        let mut a = StaticArray::zeros(vec![3, 3]);
+        a.data[4] = 2.5;
+        let runs = a.encode_rle();
+        assert_eq!(runs, vec![(0.0, 4), (2.5, 1), (0.0, 4)]);
+        assert_eq!(StaticArray::decode_rle(&runs, vec![3, 3]), a);
+
+        let nan = StaticArray::new(vec![3], vec![f32::NAN, f32::NAN, 1.0]);
+        let runs = nan.encode_rle();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].1, 2);
+        let decoded = StaticArray::decode_rle(&runs, vec![3]);
+        assert!(decoded.data[0].is_nan() && decoded.data[1].is_nan());
+    }
+
+    #[test]
+    #[should_panic]
+    fn rle_wrong_shape() {
+        StaticArray::decode_rle(&[(1.0, 3)], vec![2, 2]);
+    }
+
+    #[test]
+    fn index_value_pairs() {
+        let a = StaticArray::new(vec![2, 3], vec![0.0, 1.0, 0.0, 2.0, 0.0, 3.0]);
+        let pairs = a.to_index_value_pairs();
+        assert_eq!(pairs.len(), 6);
+        assert_eq!(pairs[0], (vec![0, 0], 0.0));
+        assert_eq!(pairs[1], (vec![0, 1], 1.0));
+        assert_eq!(pairs[3], (vec![1, 0], 2.0));
+        assert_eq!(pairs[5], (vec![1, 2], 3.0));
+
+        let nonzero: Vec<_> = pairs.into_iter().filter(|(_, v)| *v != 0.0).collect();
+        assert_eq!(nonzero.len(), 3);
+        assert_eq!(StaticArray::from_index_value_pairs(&nonzero, vec![2, 3]), a);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_value_pairs_out_of_bounds() {
+        StaticArray::from_index_value_pairs(&[(vec![2, 0], 1.0)], vec![2, 3]);
+    }
+}
@@ -0,0 +1,658 @@
+// Singly linked stack, promoted from the tutorial's `bad_stack`/`ok_stack`
+
+use std::fmt;
+
+pub struct List<T> {
+    head: Link<T>,
+    len: usize,
+}
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    elem: T,
+    next: Link<T>,
+}
+
+impl<T> List<T> {
+    pub fn new() -> Self {
+        List { head: None, len: 0 }
+    }
+
+    pub fn push(&mut self, elem: T) {
+        let new_node = Box::new(Node {
+            elem,
+            next: self.head.take(),
+        });
+        self.head = Some(new_node);
+        self.len += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.head.take().map(|node| {
+            self.head = node.next;
+            self.len -= 1;
+            node.elem
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|elem| elem == x)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.elem)
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head.as_mut().map(|node| &mut node.elem)
+    }
+
+    /// Moves every element of `other` underneath the current elements, so
+    /// `self`'s head stays on top. Only walks `self`; no nodes are reallocated.
+    pub fn append(&mut self, mut other: List<T>) {
+        let mut tail = &mut self.head;
+        while let Some(node) = tail {
+            tail = &mut node.next;
+        }
+        *tail = other.head.take();
+        self.len += other.len;
+        other.len = 0;
+    }
+
+    /// Reverses the list in place by relinking the nodes.
+    pub fn reverse(&mut self) {
+        let mut prev: Link<T> = None;
+        let mut cur = self.head.take();
+        while let Some(mut node) = cur {
+            cur = node.next.take();
+            node.next = prev;
+            prev = Some(node);
+        }
+        self.head = prev;
+    }
+
+    /// Keeps the first `n` elements (from the head) and returns the rest as a
+    /// new list. Splitting past the end returns an empty list.
+    pub fn split_off(&mut self, n: usize) -> List<T> {
+        let kept = n.min(self.len);
+        let mut cursor = &mut self.head;
+        for _ in 0..kept {
+            cursor = &mut cursor.as_mut().unwrap().next;
+        }
+        let rest = List {
+            head: cursor.take(),
+            len: self.len - kept,
+        };
+        self.len = kept;
+        rest
+    }
+
+    /// Drops every element for which `f` returns `false`, keeping the order of
+    /// the rest. Nodes are unlinked in place rather than rebuilt.
+    pub fn retain(&mut self, mut f: impl FnMut(&T) -> bool) {
+        let mut cursor = &mut self.head;
+        while cursor.is_some() {
+            if f(&cursor.as_ref().unwrap().elem) {
+                cursor = &mut cursor.as_mut().unwrap().next;
+            } else {
+                let mut removed = cursor.take().unwrap();
+                *cursor = removed.next.take();
+                self.len -= 1;
+            }
+        }
+    }
+
+    /// Unlinks and returns the first element (from the head) matching `pred`.
+    pub fn remove_first(&mut self, mut pred: impl FnMut(&T) -> bool) -> Option<T> {
+        let mut cursor = &mut self.head;
+        while cursor.is_some() {
+            if pred(&cursor.as_ref().unwrap().elem) {
+                let mut removed = cursor.take().unwrap();
+                *cursor = removed.next.take();
+                self.len -= 1;
+                return Some(removed.elem);
+            }
+            cursor = &mut cursor.as_mut().unwrap().next;
+        }
+        None
+    }
+
+    /// Inserts `elem` so a list sorted ascending from head to tail stays
+    /// sorted. Equal elements keep their insertion order.
+    pub fn insert_sorted(&mut self, elem: T)
+    where
+        T: Ord,
+    {
+        let mut cursor = &mut self.head;
+        while cursor.as_ref().is_some_and(|node| node.elem <= elem) {
+            cursor = &mut cursor.as_mut().unwrap().next;
+        }
+        let next = cursor.take();
+        *cursor = Some(Box::new(Node { elem, next }));
+        self.len += 1;
+    }
+
+    /// Merges two lists sorted ascending from head to tail by relinking their
+    /// nodes. On ties, elements of `self` come first.
+    pub fn merge(mut self, mut other: List<T>) -> List<T>
+    where
+        T: Ord,
+    {
+        let mut merged = List::new();
+        merged.len = self.len + other.len;
+        let mut a = self.head.take();
+        let mut b = other.head.take();
+        let mut tail = &mut merged.head;
+        loop {
+            let source = match (&a, &b) {
+                (Some(x), Some(y)) if x.elem <= y.elem => &mut a,
+                (Some(_), Some(_)) => &mut b,
+                // One side is exhausted, so the rest of the other is in order
+                _ => {
+                    *tail = a.or(b);
+                    break;
+                }
+            };
+            let mut node = source.take().unwrap();
+            *source = node.next.take();
+            tail = &mut tail.insert(node).next;
+        }
+        merged
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head.as_deref_mut(),
+        }
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
+}
+
+impl<T: Clone> Clone for List<T> {
+    fn clone(&self) -> Self {
+        // Append at a tail cursor rather than recursing through `next`
+        let mut new = List::new();
+        let mut tail = &mut new.head;
+        for elem in self.iter() {
+            let node = tail.insert(Box::new(Node {
+                elem: elem.clone(),
+                next: None,
+            }));
+            tail = &mut node.next;
+        }
+        new.len = self.len;
+        new
+    }
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "List")?;
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, elem) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", elem)?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Pushes the elements in order, so the last element of the `Vec` ends up on
+/// top of the stack.
+impl<T> From<Vec<T>> for List<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T> Extend<T> for List<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+/// Pushes the elements in iteration order, so the last one ends up on top.
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        // The derived drop would recurse once per node and overflow the stack
+        // on long lists, so unlink the nodes one at a time instead.
+        let mut cur_link = self.head.take();
+        while let Some(mut boxed_node) = cur_link {
+            cur_link = boxed_node.next.take();
+        }
+    }
+}
+
+pub struct IntoIter<T>(List<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.elem
+        })
+    }
+}
+
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // `take` so each node's `&mut` is handed out exactly once
+        self.next.take().map(|node| {
+            self.next = node.next.as_deref_mut();
+            &mut node.elem
+        })
+    }
+}
+
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basics() {
+        let mut list = List::new();
+        assert_eq!(list.pop(), None);
+
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(2));
+
+        list.push(4);
+        assert_eq!(list.pop(), Some(4));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+    }
+
+    #[test]
+    fn peek() {
+        let mut list = List::new();
+        assert_eq!(list.peek(), None);
+        assert_eq!(list.peek_mut(), None);
+
+        list.push(1);
+        list.push(2);
+        assert_eq!(list.peek(), Some(&2));
+        assert_eq!(list.peek_mut(), Some(&mut 2));
+    }
+
+    #[test]
+    fn peek_mut() {
+        let mut list = List::new();
+        list.push(1);
+        list.push(2);
+
+        *list.peek_mut().unwrap() += 40;
+        assert_eq!(list.peek(), Some(&42));
+        assert_eq!(list.pop(), Some(42));
+        assert_eq!(list.pop(), Some(1));
+    }
+
+    #[test]
+    fn into_iter() {
+        let mut list = List::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn iter() {
+        let mut list = List::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+        let mut seen = Vec::new();
+        for elem in &list {
+            seen.push(*elem);
+        }
+        assert_eq!(seen, vec![3, 2, 1]);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut list = List::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+
+        for elem in list.iter_mut() {
+            *elem *= 2;
+        }
+        for elem in &mut list {
+            *elem += 1;
+        }
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![7, 5, 3]);
+    }
+
+    #[test]
+    fn len_and_contains() {
+        let mut list = List::new();
+        assert!(list.is_empty());
+        assert!(!list.contains(&1));
+
+        list.push(1);
+        list.push(2);
+        assert_eq!(list.len(), 2);
+        assert!(!list.is_empty());
+        assert!(list.contains(&1));
+        assert!(!list.contains(&3));
+    }
+
+    #[test]
+    fn len_tracks_operations() {
+        let mut list = List::new();
+        let mut shadow = Vec::new();
+        let mut state: u32 = 12345;
+        for i in 0..1000 {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            if (state >> 16).is_multiple_of(3) {
+                assert_eq!(list.pop(), shadow.pop());
+            } else {
+                list.push(i);
+                shadow.push(i);
+            }
+            assert_eq!(list.len(), shadow.len());
+        }
+    }
+
+    #[test]
+    fn clone_and_eq() {
+        let mut list = List::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+
+        let mut copy = list.clone();
+        assert_eq!(copy, list);
+        assert_eq!(copy.len(), 3);
+        *copy.peek_mut().unwrap() = 30;
+        assert_ne!(copy, list);
+        assert_eq!(list.peek(), Some(&3));
+
+        copy.pop();
+        assert_ne!(copy, list);
+        assert_ne!(List::<i32>::new(), list);
+    }
+
+    #[test]
+    fn fmt() {
+        let mut list = List::new();
+        assert_eq!(format!("{:?}", list), "List[]");
+        assert_eq!(list.to_string(), "[]");
+
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        assert_eq!(format!("{:?}", list), "List[3, 2, 1]");
+        assert_eq!(list.to_string(), "[3, 2, 1]");
+
+        let mut names = List::new();
+        names.push("a");
+        assert_eq!(format!("{:?}", names), "List[\"a\"]");
+    }
+
+    #[test]
+    fn long_list_clone() {
+        let mut list = List::new();
+        for i in 0..100_000 {
+            list.push(i);
+        }
+        let copy = list.clone();
+        assert_eq!(copy.len(), 100_000);
+        assert!(copy == list);
+    }
+
+    #[test]
+    fn from_vec_and_collect() {
+        let list = List::from(vec![1, 2, 3]);
+        assert_eq!(list.peek(), Some(&3));
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+
+        let list: List<_> = (0..10).collect();
+        assert_eq!(list.len(), 10);
+        assert_eq!(list.peek(), Some(&9));
+
+        let mut list = List::from(vec![1]);
+        list.extend(vec![2, 3]);
+        assert_eq!(list, List::from(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn append() {
+        let mut top = List::from(vec![3, 4]);
+        let bottom = List::from(vec![1, 2]);
+        top.append(bottom);
+        assert_eq!(top.len(), 4);
+        assert_eq!(top.iter().copied().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+
+        let mut empty = List::new();
+        empty.append(List::from(vec![1]));
+        assert_eq!(empty, List::from(vec![1]));
+        empty.append(List::new());
+        assert_eq!(empty.len(), 1);
+    }
+
+    #[test]
+    fn reverse() {
+        let original: List<_> = (0..5).collect();
+        let mut list = original.clone();
+        list.reverse();
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+        assert_eq!(list.len(), 5);
+        list.reverse();
+        assert_eq!(list, original);
+
+        let mut empty = List::<i32>::new();
+        empty.reverse();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn split_off() {
+        let mut list: List<_> = (0..5).collect();
+        let rest = list.split_off(2);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![4, 3]);
+        assert_eq!(rest.iter().copied().collect::<Vec<_>>(), vec![2, 1, 0]);
+        assert_eq!(list.len() + rest.len(), 5);
+
+        let mut list: List<_> = (0..5).collect();
+        let all = list.split_off(0);
+        assert!(list.is_empty());
+        assert_eq!(all.len(), 5);
+
+        let mut list: List<_> = (0..5).collect();
+        let none = list.split_off(10);
+        assert!(none.is_empty());
+        assert_eq!(list.len(), 5);
+    }
+
+    #[test]
+    fn insert_sorted() {
+        let mut list = List::new();
+        for x in [5, 1, 4, 1, 9, 2, 6, 5, 3] {
+            list.insert_sorted(x);
+        }
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 1, 2, 3, 4, 5, 5, 6, 9]
+        );
+        assert_eq!(list.len(), 9);
+    }
+
+    #[test]
+    fn merge() {
+        let mut a = List::new();
+        let mut b = List::new();
+        for x in [1, 3, 5, 5, 8] {
+            a.insert_sorted(x);
+        }
+        for x in [2, 3, 4, 9, 10] {
+            b.insert_sorted(x);
+        }
+        let merged = a.merge(b);
+        assert_eq!(merged.len(), 10);
+        assert_eq!(
+            merged.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 3, 4, 5, 5, 8, 9, 10]
+        );
+
+        let merged = List::new().merge(List::from(vec![1]));
+        assert_eq!(merged, List::from(vec![1]));
+    }
+
+    #[test]
+    fn retain() {
+        let mut list: List<_> = (0..20).collect();
+        list.retain(|x| x % 2 == 0);
+        assert_eq!(list.len(), 10);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            (0..20).rev().filter(|x| x % 2 == 0).collect::<Vec<_>>()
+        );
+
+        list.retain(|_| false);
+        assert!(list.is_empty());
+        assert_eq!(list.peek(), None);
+    }
+
+    #[test]
+    fn remove_first() {
+        let mut list: List<_> = (0..5).collect();
+        assert_eq!(list.remove_first(|&x| x == 2), Some(2));
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![4, 3, 1, 0]);
+        assert_eq!(list.remove_first(|&x| x == 2), None);
+
+        assert_eq!(list.remove_first(|&x| x == 4), Some(4));
+        assert_eq!(list.remove_first(|&x| x == 0), Some(0));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 1]);
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn owned_elements() {
+        let mut list = List::new();
+        list.push(String::from("a"));
+        list.push(String::from("b"));
+        assert_eq!(list.pop(), Some(String::from("b")));
+    }
+
+    #[test]
+    fn long_list_drop() {
+        let mut list = List::new();
+        for i in 0..100_000 {
+            list.push(i.to_string());
+        }
+        drop(list);
+    }
+}
@@ -1 +1,4 @@
+pub mod deque;
 pub mod ds_array;
+pub mod list;
+pub mod persistent_list;
@@ -0,0 +1,150 @@
+// Immutable singly linked list whose versions share their tails through `Rc`
+
+use std::rc::Rc;
+
+pub struct PersistentList<T> {
+    head: Link<T>,
+}
+
+type Link<T> = Option<Rc<Node<T>>>;
+
+struct Node<T> {
+    elem: T,
+    next: Link<T>,
+}
+
+impl<T> PersistentList<T> {
+    pub fn new() -> Self {
+        PersistentList { head: None }
+    }
+
+    /// New list with `elem` in front of this one, sharing all of its nodes.
+    pub fn prepend(&self, elem: T) -> PersistentList<T> {
+        PersistentList {
+            head: Some(Rc::new(Node {
+                elem,
+                next: self.head.clone(),
+            })),
+        }
+    }
+
+    /// New list without the first element, sharing the remaining nodes.
+    pub fn tail(&self) -> PersistentList<T> {
+        PersistentList {
+            head: self.head.as_ref().and_then(|node| node.next.clone()),
+        }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.elem)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+}
+
+impl<T> Default for PersistentList<T> {
+    fn default() -> Self {
+        PersistentList::new()
+    }
+}
+
+impl<T> Drop for PersistentList<T> {
+    fn drop(&mut self) {
+        // Free nodes iteratively while this list is their only owner; the
+        // first shared node is left to whichever list still points at it.
+        let mut head = self.head.take();
+        while let Some(node) = head {
+            if let Ok(mut node) = Rc::try_unwrap(node) {
+                head = node.next.take();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.elem
+        })
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PersistentList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basics() {
+        let list = PersistentList::new();
+        assert_eq!(list.head(), None);
+
+        let list = list.prepend(1).prepend(2).prepend(3);
+        assert_eq!(list.head(), Some(&3));
+
+        let list = list.tail();
+        assert_eq!(list.head(), Some(&2));
+        let list = list.tail();
+        assert_eq!(list.head(), Some(&1));
+        let list = list.tail();
+        assert_eq!(list.head(), None);
+        let list = list.tail();
+        assert_eq!(list.head(), None);
+    }
+
+    #[test]
+    fn iter() {
+        let list = PersistentList::new().prepend(1).prepend(2).prepend(3);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+        assert_eq!((&list).into_iter().count(), 3);
+    }
+
+    #[test]
+    fn shared_tail() {
+        let shared = PersistentList::new()
+            .prepend(String::from("c"))
+            .prepend(String::from("b"));
+        let first = shared.prepend(String::from("a1"));
+        let second = shared.prepend(String::from("a2"));
+        drop(shared);
+
+        drop(first);
+        assert_eq!(
+            second.iter().map(String::as_str).collect::<Vec<_>>(),
+            vec!["a2", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn long_list_drop() {
+        let mut list = PersistentList::new();
+        for i in 0..100_000 {
+            list = list.prepend(i);
+        }
+        let shared = list.tail();
+        drop(list);
+        assert_eq!(shared.head(), Some(&99_998));
+        drop(shared);
+    }
+}
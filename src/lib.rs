@@ -1 +1,8 @@
 pub mod data_structures;
+pub mod linalg;
+pub mod utils;
+
+pub use data_structures::deque::Deque;
+pub use data_structures::ds_array::static_array::StaticArray;
+pub use data_structures::list::List;
+pub use data_structures::persistent_list::PersistentList;
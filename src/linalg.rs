@@ -0,0 +1,683 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::StaticArray;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArrayError {
+    NotPositiveDefinite,
+    Singular,
+}
+
+impl fmt::Display for ArrayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArrayError::NotPositiveDefinite => write!(f, "matrix is not positive definite"),
+            ArrayError::Singular => write!(f, "matrix is singular"),
+        }
+    }
+}
+
+impl Error for ArrayError {}
+
+/// Side length of a square 2D array, panicking for any other shape.
+fn square_size(a: &StaticArray) -> usize {
+    assert!(
+        a.shape.len() == 2 && a.shape[0] == a.shape[1],
+        "expected a square 2D array, got shape {:?}",
+        a.shape
+    );
+    a.shape[0]
+}
+
+fn identity(n: usize) -> StaticArray {
+    let mut out = StaticArray::zeros(vec![n, n]);
+    for i in 0..n {
+        out.data[i * n + i] = 1.0;
+    }
+    out
+}
+
+/// Product of two square matrices of the same size.
+fn matmul(a: &StaticArray, b: &StaticArray) -> StaticArray {
+    let n = a.shape[0];
+    let mut out = StaticArray::zeros(vec![n, n]);
+    for i in 0..n {
+        for k in 0..n {
+            let aik = a.data[i * n + k];
+            for j in 0..n {
+                out.data[i * n + j] += aik * b.data[k * n + j];
+            }
+        }
+    }
+    out
+}
+
+/// Inverse of a square matrix by Gauss-Jordan elimination with partial
+/// pivoting.
+pub fn inv(a: &StaticArray) -> Result<StaticArray, ArrayError> {
+    let n = square_size(a);
+    let mut m: Vec<f64> = a.data.iter().map(|&x| x as f64).collect();
+    let mut out: Vec<f64> = identity(n).data.iter().map(|&x| x as f64).collect();
+    let scale = m.iter().fold(0.0f64, |acc, x| acc.max(x.abs()));
+
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&x, &y| m[x * n + col].abs().total_cmp(&m[y * n + col].abs()))
+            .unwrap();
+        if m[pivot * n + col].abs() <= n as f64 * f32::EPSILON as f64 * scale {
+            return Err(ArrayError::Singular);
+        }
+        for j in 0..n {
+            m.swap(col * n + j, pivot * n + j);
+            out.swap(col * n + j, pivot * n + j);
+        }
+        let diag = m[col * n + col];
+        for j in 0..n {
+            m[col * n + j] /= diag;
+            out[col * n + j] /= diag;
+        }
+        for row in 0..n {
+            let factor = m[row * n + col];
+            if row == col || factor == 0.0 {
+                continue;
+            }
+            for j in 0..n {
+                m[row * n + j] -= factor * m[col * n + j];
+                out[row * n + j] -= factor * out[col * n + j];
+            }
+        }
+    }
+    Ok(StaticArray::new(
+        vec![n, n],
+        out.iter().map(|&x| x as f32).collect(),
+    ))
+}
+
+/// `a` raised to the integer power `n` by repeated squaring. Negative powers
+/// invert `a` first, and `n == 0` gives the identity.
+pub fn matrix_power(a: &StaticArray, n: i32) -> StaticArray {
+    let size = square_size(a);
+    let mut base = if n < 0 {
+        inv(a).expect("negative power of a singular matrix")
+    } else {
+        a.clone()
+    };
+    let mut exponent = n.unsigned_abs();
+    let mut result = identity(size);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = matmul(&result, &base);
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base = matmul(&base, &base);
+        }
+    }
+    result
+}
+
+/// Matrix exponential using an order 6 Padé approximation with scaling and
+/// squaring.
+pub fn expm(a: &StaticArray) -> StaticArray {
+    let n = square_size(a);
+    const Q: usize = 6;
+
+    // Scale so the infinity norm is at most 1/2, then square the result back up
+    let norm = (0..n)
+        .map(|i| {
+            a.data[i * n..(i + 1) * n]
+                .iter()
+                .map(|x| x.abs())
+                .sum::<f32>()
+        })
+        .fold(0.0f32, f32::max);
+    assert!(
+        norm.is_finite() && a.data.iter().all(|x| x.is_finite()),
+        "expm needs finite input whose row sums fit in an f32"
+    );
+    let squarings = if norm > 0.0 {
+        (norm.log2().ceil() as i32 + 1).max(0)
+    } else {
+        0
+    };
+    let factor = 0.5f32.powi(squarings);
+    let scaled = StaticArray::new(a.shape.clone(), a.data.iter().map(|x| x * factor).collect());
+
+    let mut numer = identity(n);
+    let mut denom = identity(n);
+    let mut power = identity(n);
+    let mut c = 1.0f32;
+    for k in 1..=Q {
+        c *= (Q - k + 1) as f32 / (k * (2 * Q - k + 1)) as f32;
+        power = matmul(&scaled, &power);
+        let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+        for i in 0..n * n {
+            numer.data[i] += c * power.data[i];
+            denom.data[i] += sign * c * power.data[i];
+        }
+    }
+
+    let mut result = matmul(&inv(&denom).expect("Padé denominator is singular"), &numer);
+    for _ in 0..squarings {
+        result = matmul(&result, &result);
+    }
+    result
+}
+
+/// Block-diagonal matrix with `blocks` along the diagonal and zeros elsewhere.
+///
+/// 2D blocks may be rectangular. Each element of a 1D block becomes its own
+/// 1x1 block, so a 1D input contributes a diagonal run of its values.
+pub fn block_diag(blocks: &[&StaticArray]) -> StaticArray {
+    let dims: Vec<(usize, usize)> = blocks
+        .iter()
+        .map(|block| match block.shape.len() {
+            1 => (block.shape[0], block.shape[0]),
+            2 => (block.shape[0], block.shape[1]),
+            _ => panic!("expected 1D or 2D blocks, got shape {:?}", block.shape),
+        })
+        .collect();
+    let rows: usize = dims.iter().map(|d| d.0).sum();
+    let cols: usize = dims.iter().map(|d| d.1).sum();
+
+    let mut out = StaticArray::zeros(vec![rows, cols]);
+    let (mut row0, mut col0) = (0, 0);
+    for (block, &(r, c)) in blocks.iter().zip(dims.iter()) {
+        if block.shape.len() == 1 {
+            for (k, &value) in block.data.iter().enumerate() {
+                out.data[(row0 + k) * cols + col0 + k] = value;
+            }
+        } else {
+            for i in 0..r {
+                out.data[(row0 + i) * cols + col0..(row0 + i) * cols + col0 + c]
+                    .copy_from_slice(&block.data[i * c..(i + 1) * c]);
+            }
+        }
+        row0 += r;
+        col0 += c;
+    }
+    out
+}
+
+/// Cholesky factor `L` of a symmetric positive-definite matrix, so that
+/// `A = L * L^T`. Only the lower triangle of `a` is read.
+pub fn chol(a: &StaticArray) -> Result<StaticArray, ArrayError> {
+    let n = square_size(a);
+    let mut l = StaticArray::zeros(vec![n, n]);
+    for j in 0..n {
+        let mut pivot = a.data[j * n + j];
+        for k in 0..j {
+            pivot -= l.data[j * n + k] * l.data[j * n + k];
+        }
+        if pivot <= 0.0 || pivot.is_nan() {
+            return Err(ArrayError::NotPositiveDefinite);
+        }
+        let diag = pivot.sqrt();
+        l.data[j * n + j] = diag;
+        for i in j + 1..n {
+            let mut value = a.data[i * n + j];
+            for k in 0..j {
+                value -= l.data[i * n + k] * l.data[j * n + k];
+            }
+            l.data[i * n + j] = value / diag;
+        }
+    }
+    Ok(l)
+}
+
+/// Eigenvalues (ascending) and eigenvectors (as columns) of a symmetric
+/// matrix, computed with the cyclic Jacobi method.
+pub fn eigh(a: &StaticArray) -> (StaticArray, StaticArray) {
+    let n = square_size(a);
+    let scale = a.data.iter().fold(0.0f32, |acc, x| acc.max(x.abs()));
+    for i in 0..n {
+        for j in i + 1..n {
+            assert!(
+                (a.data[i * n + j] - a.data[j * n + i]).abs() <= 1e-5 * scale,
+                "matrix is not symmetric at ({}, {})",
+                i,
+                j
+            );
+        }
+    }
+
+    let mut m: Vec<f64> = a.data.iter().map(|&x| x as f64).collect();
+    // Converged once the off-diagonal mass is negligible relative to `a`
+    let frob2: f64 = m.iter().map(|x| x * x).sum();
+    let mut v = vec![0.0f64; n * n];
+    for i in 0..n {
+        v[i * n + i] = 1.0;
+    }
+
+    for _ in 0..100 {
+        let off: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| m[i * n + j].powi(2))
+            .sum();
+        if off <= f64::EPSILON * f64::EPSILON * frob2 {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = m[p * n + q];
+                if apq == 0.0 {
+                    continue;
+                }
+                // Rotation angle that zeroes m[p][q]
+                let theta = (m[q * n + q] - m[p * n + p]) / (2.0 * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let (mkp, mkq) = (m[k * n + p], m[k * n + q]);
+                    m[k * n + p] = c * mkp - s * mkq;
+                    m[k * n + q] = s * mkp + c * mkq;
+                }
+                for k in 0..n {
+                    let (mpk, mqk) = (m[p * n + k], m[q * n + k]);
+                    m[p * n + k] = c * mpk - s * mqk;
+                    m[q * n + k] = s * mpk + c * mqk;
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (v[k * n + p], v[k * n + q]);
+                    v[k * n + p] = c * vkp - s * vkq;
+                    v[k * n + q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&x, &y| m[x * n + x].total_cmp(&m[y * n + y]));
+    let values = order.iter().map(|&k| m[k * n + k] as f32).collect();
+    let mut vectors = StaticArray::zeros(vec![n, n]);
+    for (col, &k) in order.iter().enumerate() {
+        for row in 0..n {
+            vectors.data[row * n + col] = v[row * n + k] as f32;
+        }
+    }
+    (StaticArray::new(vec![n], values), vectors)
+}
+
+/// Output of [`lstsq`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LstsqResult {
+    pub solution: StaticArray,
+    /// Sum of squared residuals `||Ax - b||^2`.
+    pub residuals: f32,
+    /// Numerical rank of `a`.
+    pub rank: usize,
+}
+
+/// Least-squares solution of `Ax = b` for a 2D `a` of shape `[m, n]` and a 1D
+/// `b` of length `m`.
+///
+/// Uses a column-pivoted Householder QR. When `a` is rank deficient the
+/// components beyond the rank are set to zero.
+pub fn lstsq(a: &StaticArray, b: &StaticArray) -> LstsqResult {
+    assert_eq!(
+        a.shape.len(),
+        2,
+        "expected a 2D array, got shape {:?}",
+        a.shape
+    );
+    let (m, n) = (a.shape[0], a.shape[1]);
+    assert_eq!(
+        b.shape,
+        vec![m],
+        "expected b of shape [{}] for a of shape {:?}",
+        m,
+        a.shape
+    );
+
+    let mut r: Vec<f64> = a.data.iter().map(|&x| x as f64).collect();
+    let mut qtb: Vec<f64> = b.data.iter().map(|&x| x as f64).collect();
+    let mut perm: Vec<usize> = (0..n).collect();
+    let steps = m.min(n);
+
+    for k in 0..steps {
+        // Move the remaining column with the largest norm into position k
+        let col_norm = |r: &[f64], j: usize| (k..m).map(|i| r[i * n + j].powi(2)).sum::<f64>();
+        let pivot = (k..n)
+            .max_by(|&x, &y| col_norm(&r, x).total_cmp(&col_norm(&r, y)))
+            .unwrap();
+        if pivot != k {
+            for i in 0..m {
+                r.swap(i * n + k, i * n + pivot);
+            }
+            perm.swap(k, pivot);
+        }
+
+        // Householder reflection zeroing column k below the diagonal
+        let norm = col_norm(&r, k).sqrt();
+        if norm == 0.0 {
+            break;
+        }
+        let alpha = if r[k * n + k] > 0.0 { -norm } else { norm };
+        let mut v: Vec<f64> = (k..m).map(|i| r[i * n + k]).collect();
+        v[0] -= alpha;
+        let v_norm2: f64 = v.iter().map(|x| x * x).sum();
+        if v_norm2 == 0.0 {
+            continue;
+        }
+        for j in k..n {
+            let s: f64 = (k..m).map(|i| v[i - k] * r[i * n + j]).sum();
+            for i in k..m {
+                r[i * n + j] -= 2.0 * s / v_norm2 * v[i - k];
+            }
+        }
+        let s: f64 = (k..m).map(|i| v[i - k] * qtb[i]).sum();
+        for i in k..m {
+            qtb[i] -= 2.0 * s / v_norm2 * v[i - k];
+        }
+    }
+
+    let tol = m.max(n) as f64 * f32::EPSILON as f64 * r.first().map_or(0.0, |x| x.abs());
+    let rank = (0..steps).take_while(|&k| r[k * n + k].abs() > tol).count();
+
+    let mut z = vec![0.0; n];
+    for i in (0..rank).rev() {
+        let s: f64 = (i + 1..rank).map(|j| r[i * n + j] * z[j]).sum();
+        z[i] = (qtb[i] - s) / r[i * n + i];
+    }
+    let mut solution = StaticArray::zeros(vec![n]);
+    for (k, &col) in perm.iter().enumerate() {
+        solution.data[col] = z[k] as f32;
+    }
+    let residuals = qtb[rank..].iter().map(|x| x * x).sum::<f64>() as f32;
+
+    LstsqResult {
+        solution,
+        residuals,
+        rank,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &StaticArray, b: &StaticArray, tol: f32) {
+        assert_eq!(a.shape, b.shape);
+        for (x, y) in a.data.iter().zip(b.data.iter()) {
+            assert!((x - y).abs() <= tol, "{} != {}\n{}\n{}", x, y, a, b);
+        }
+    }
+
+    fn matmul_transposed(l: &StaticArray) -> StaticArray {
+        let n = l.shape[0];
+        let mut out = StaticArray::zeros(vec![n, n]);
+        for i in 0..n {
+            for j in 0..n {
+                out.data[i * n + j] = (0..n).map(|k| l.data[i * n + k] * l.data[j * n + k]).sum();
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn chol_spd() {
+        let a = StaticArray::new(
+            vec![3, 3],
+            vec![4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0],
+        );
+        let l = chol(&a).unwrap();
+        let expected = StaticArray::new(
+            vec![3, 3],
+            vec![2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0],
+        );
+        assert_close(&l, &expected, 1e-5);
+        assert_close(&matmul_transposed(&l), &a, 1e-4);
+    }
+
+    #[test]
+    fn chol_not_spd() {
+        let a = StaticArray::new(vec![2, 2], vec![1.0, 2.0, 2.0, 1.0]);
+        assert_eq!(chol(&a), Err(ArrayError::NotPositiveDefinite));
+    }
+
+    #[test]
+    #[should_panic]
+    fn chol_not_square() {
+        let _ = chol(&StaticArray::zeros(vec![2, 3]));
+    }
+
+    fn squared_error(a: &StaticArray, x: &[f32], b: &StaticArray) -> f32 {
+        let n = a.shape[1];
+        (0..a.shape[0])
+            .map(|i| {
+                let ax: f32 = (0..n).map(|j| a.data[i * n + j] * x[j]).sum();
+                (ax - b.data[i]).powi(2)
+            })
+            .sum()
+    }
+
+    #[test]
+    fn lstsq_exact() {
+        // y = 1 + 2x sampled at x = 0..3
+        let a = StaticArray::new(vec![4, 2], vec![1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0]);
+        let b = StaticArray::new(vec![4], vec![1.0, 3.0, 5.0, 7.0]);
+        let result = lstsq(&a, &b);
+        assert_eq!(result.rank, 2);
+        assert_close(
+            &result.solution,
+            &StaticArray::new(vec![2], vec![1.0, 2.0]),
+            1e-5,
+        );
+        assert!(result.residuals < 1e-8);
+    }
+
+    #[test]
+    fn lstsq_overdetermined() {
+        let a = StaticArray::new(vec![4, 2], vec![1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0]);
+        let b = StaticArray::new(vec![4], vec![1.0, 3.2, 4.9, 7.1]);
+        let result = lstsq(&a, &b);
+        // Normal equations: [[4, 6], [6, 14]] x = [16.2, 34.3]
+        assert_close(
+            &result.solution,
+            &StaticArray::new(vec![2], vec![1.05, 2.0]),
+            1e-5,
+        );
+        let x = &result.solution.data;
+        let best = squared_error(&a, x, &b);
+        assert!((result.residuals - best).abs() < 1e-5);
+        assert!(result.residuals < 0.1);
+        for delta in [[1e-2, 0.0], [0.0, 1e-2], [-1e-2, 1e-2]] {
+            let moved = [x[0] + delta[0], x[1] + delta[1]];
+            assert!(squared_error(&a, &moved, &b) > best);
+        }
+    }
+
+    #[test]
+    fn lstsq_rank_deficient() {
+        let a = StaticArray::new(vec![3, 2], vec![1.0, 2.0, 2.0, 4.0, 3.0, 6.0]);
+        let b = StaticArray::new(vec![3], vec![1.0, 2.0, 3.0]);
+        let result = lstsq(&a, &b);
+        assert_eq!(result.rank, 1);
+        assert!(squared_error(&a, &result.solution.data, &b) < 1e-8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn lstsq_mismatched_shapes() {
+        let _ = lstsq(
+            &StaticArray::zeros(vec![3, 2]),
+            &StaticArray::zeros(vec![2]),
+        );
+    }
+
+    #[test]
+    fn eigh_known() {
+        let a = StaticArray::new(
+            vec![3, 3],
+            vec![2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0],
+        );
+        let (values, vectors) = eigh(&a);
+        let sqrt2 = 2f32.sqrt();
+        assert_close(
+            &values,
+            &StaticArray::new(vec![3], vec![2.0 - sqrt2, 2.0, 2.0 + sqrt2]),
+            1e-5,
+        );
+        for k in 0..3 {
+            let column: Vec<f32> = (0..3).map(|i| vectors.data[i * 3 + k]).collect();
+            let norm: f32 = column.iter().map(|x| x * x).sum::<f32>().sqrt();
+            assert!((norm - 1.0).abs() < 1e-5);
+            for i in 0..3 {
+                let av: f32 = (0..3).map(|j| a.data[i * 3 + j] * column[j]).sum();
+                assert!((av - values.data[k] * column[i]).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn eigh_small_magnitude() {
+        let a = StaticArray::new(vec![2, 2], vec![1e-13; 4]);
+        let (values, _) = eigh(&a);
+        assert!(values.data[0].abs() < 1e-19);
+        assert!((values.data[1] - 2e-13).abs() < 1e-19);
+
+        let scale = 1e-13f32;
+        let a = StaticArray::new(
+            vec![3, 3],
+            [2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0]
+                .iter()
+                .map(|x| x * scale)
+                .collect(),
+        );
+        let (values, _) = eigh(&a);
+        let sqrt2 = 2f32.sqrt();
+        for (value, expected) in values.data.iter().zip([2.0 - sqrt2, 2.0, 2.0 + sqrt2]) {
+            assert!((value / scale - expected).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn eigh_not_symmetric_small() {
+        let _ = eigh(&StaticArray::new(vec![2, 2], vec![0.0, 1e-6, -1e-6, 0.0]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn eigh_not_symmetric() {
+        let _ = eigh(&StaticArray::new(vec![2, 2], vec![1.0, 2.0, 3.0, 4.0]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn eigh_not_square() {
+        let _ = eigh(&StaticArray::zeros(vec![2, 3]));
+    }
+
+    fn invertible() -> StaticArray {
+        StaticArray::new(
+            vec![3, 3],
+            vec![2.0, 1.0, 0.0, 1.0, 3.0, 1.0, 0.0, 1.0, 4.0],
+        )
+    }
+
+    #[test]
+    fn inv_roundtrip() {
+        let a = invertible();
+        let a_inv = inv(&a).unwrap();
+        assert_close(&matmul(&a, &a_inv), &identity(3), 1e-6);
+        assert_close(&matmul(&a_inv, &a), &identity(3), 1e-6);
+    }
+
+    #[test]
+    fn inv_singular() {
+        let a = StaticArray::new(vec![2, 2], vec![1.0, 2.0, 2.0, 4.0]);
+        assert_eq!(inv(&a), Err(ArrayError::Singular));
+    }
+
+    #[test]
+    fn matrix_power_values() {
+        let a = invertible();
+        assert_eq!(matrix_power(&a, 0), identity(3));
+        assert_eq!(matrix_power(&a, 1), a);
+        assert_close(&matrix_power(&a, 3), &matmul(&matmul(&a, &a), &a), 1e-4);
+        assert_close(&matrix_power(&a, -1), &inv(&a).unwrap(), 1e-6);
+        let a_inv = inv(&a).unwrap();
+        assert_close(&matrix_power(&a, -2), &matmul(&a_inv, &a_inv), 1e-6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn matrix_power_singular_negative() {
+        let _ = matrix_power(&StaticArray::zeros(vec![2, 2]), -1);
+    }
+
+    #[test]
+    fn expm_zeros() {
+        assert_eq!(expm(&StaticArray::zeros(vec![3, 3])), identity(3));
+    }
+
+    #[test]
+    fn expm_diagonal() {
+        let mut a = StaticArray::zeros(vec![3, 3]);
+        a.data[0] = 1.0;
+        a.data[4] = 2.0;
+        a.data[8] = -1.0;
+        let mut expected = StaticArray::zeros(vec![3, 3]);
+        expected.data[0] = 1f32.exp();
+        expected.data[4] = 2f32.exp();
+        expected.data[8] = (-1f32).exp();
+        assert_close(&expm(&a), &expected, 1e-5);
+    }
+
+    #[test]
+    #[should_panic(expected = "finite")]
+    fn expm_overflowing_norm() {
+        let _ = expm(&StaticArray::new(
+            vec![2, 2],
+            vec![f32::MAX, f32::MAX, 0.0, 0.0],
+        ));
+    }
+
+    #[test]
+    fn expm_rotation() {
+        let t = 2.5f32;
+        let a = StaticArray::new(vec![2, 2], vec![0.0, -t, t, 0.0]);
+        let expected = StaticArray::new(vec![2, 2], vec![t.cos(), -t.sin(), t.sin(), t.cos()]);
+        assert_close(&expm(&a), &expected, 1e-5);
+    }
+
+    #[test]
+    fn block_diag_2d() {
+        let a = StaticArray::new(vec![2, 2], vec![1.0, 2.0, 3.0, 4.0]);
+        let b = StaticArray::new(vec![2, 2], vec![5.0, 6.0, 7.0, 8.0]);
+        let expected = StaticArray::new(
+            vec![4, 4],
+            vec![
+                1.0, 2.0, 0.0, 0.0, //
+                3.0, 4.0, 0.0, 0.0, //
+                0.0, 0.0, 5.0, 6.0, //
+                0.0, 0.0, 7.0, 8.0,
+            ],
+        );
+        assert_eq!(block_diag(&[&a, &b]), expected);
+    }
+
+    #[test]
+    fn block_diag_mixed() {
+        let a = StaticArray::new(vec![2], vec![1.0, 2.0]);
+        let b = StaticArray::new(vec![1, 2], vec![3.0, 4.0]);
+        let expected = StaticArray::new(
+            vec![3, 4],
+            vec![
+                1.0, 0.0, 0.0, 0.0, //
+                0.0, 2.0, 0.0, 0.0, //
+                0.0, 0.0, 3.0, 4.0,
+            ],
+        );
+        assert_eq!(block_diag(&[&a, &b]), expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn block_diag_3d() {
+        let _ = block_diag(&[&StaticArray::zeros(vec![1, 1, 1])]);
+    }
+}
@@ -0,0 +1,88 @@
+use std::mem;
+
+/// Number of bytes needed to store an `f32` array of the given shape.
+pub fn memory_footprint_bytes(shape: &[usize]) -> usize {
+    shape.iter().product::<usize>() * mem::size_of::<f32>()
+}
+
+/// Number of contiguous slices along axis 0 that fit in `bytes_available`.
+///
+/// The result is never larger than the axis length and never smaller than 1,
+/// so it can be used directly as a chunk size even on a tight budget.
+pub fn chunk_size_estimate(shape: &[usize], bytes_available: usize) -> usize {
+    let Some((&rows, rest)) = shape.split_first() else {
+        return 1;
+    };
+    let row_bytes = memory_footprint_bytes(rest);
+    if row_bytes == 0 {
+        return rows.max(1);
+    }
+    (bytes_available / row_bytes).min(rows).max(1)
+}
+
+/// Row and column indices of the main diagonal of an `n x n` matrix.
+pub fn diag_indices(n: usize) -> (Vec<usize>, Vec<usize>) {
+    ((0..n).collect(), (0..n).collect())
+}
+
+/// Row and column indices of the lower triangle of an `n x n` matrix, in
+/// row-major order. `k` offsets the diagonal: positive values include that
+/// many diagonals above the main one, negative values exclude diagonals.
+pub fn tril_indices(n: usize, k: i32) -> (Vec<usize>, Vec<usize>) {
+    let mut rows = Vec::new();
+    let mut cols = Vec::new();
+    for i in 0..n {
+        for j in 0..n {
+            if j as i64 <= i as i64 + k as i64 {
+                rows.push(i);
+                cols.push(j);
+            }
+        }
+    }
+    (rows, cols)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn footprint() {
+        assert_eq!(memory_footprint_bytes(&[1000, 1000]), 4_000_000);
+        assert_eq!(memory_footprint_bytes(&[]), 4);
+    }
+
+    #[test]
+    fn chunk_size() {
+        let shape = [1000, 1000];
+        // One row is 4000 bytes
+        assert_eq!(chunk_size_estimate(&shape, 40_000), 10);
+        assert_eq!(chunk_size_estimate(&shape, 41_999), 10);
+        assert_eq!(chunk_size_estimate(&shape, 10), 1);
+        assert_eq!(chunk_size_estimate(&shape, usize::MAX), 1000);
+    }
+
+    #[test]
+    fn diag() {
+        let (rows, cols) = diag_indices(3);
+        assert_eq!(rows, vec![0, 1, 2]);
+        assert_eq!(cols, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn tril() {
+        let (rows, cols) = tril_indices(3, 0);
+        assert_eq!(rows, vec![0, 1, 1, 2, 2, 2]);
+        assert_eq!(cols, vec![0, 0, 1, 0, 1, 2]);
+        for (i, j) in rows.iter().zip(cols.iter()) {
+            assert!(j <= i);
+        }
+
+        let (rows, cols) = tril_indices(3, -1);
+        assert_eq!(rows, vec![1, 2, 2]);
+        assert_eq!(cols, vec![0, 0, 1]);
+
+        let (rows, _) = tril_indices(3, 2);
+        assert_eq!(rows.len(), 9);
+    }
+}